@@ -1,5 +1,12 @@
 use crate::settings::AppSettings;
 
+/// `update` is meant to run on the overlay's dedicated update thread (see
+/// `overlay::System::main_loop`) and is the only place enhancements should
+/// read live game memory. `render` is meant to run on the render thread
+/// against the `ViewController` built from the most recently published
+/// update, so it should never reach back into shared mutable state itself.
+/// Enforcing that split is the caller's job: it has to build the
+/// `ViewController` snapshot inside the closure it hands to `main_loop`.
 pub trait Enhancement {
     fn update(&mut self, ctx: &UpdateContext) -> anyhow::Result<()>;
     fn render(&self, settings: &AppSettings, ui: &imgui::Ui, view: &ViewController);
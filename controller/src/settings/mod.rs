@@ -56,6 +56,13 @@ pub struct AppSettings {
     #[serde(default = "bool_false")]
     pub aim_assist_recoil: bool,
 
+    /// Toggle for shaping player names and bomb-site labels with `rustybuzz`
+    /// (see `overlay::shape_line`). No render path uses it (see
+    /// `overlay::init`'s `text_shaping` parameter): enabling it has no
+    /// effect on rendering and only logs a warning.
+    #[serde(default = "bool_false")]
+    pub text_shaping: bool,
+
     #[serde(default)]
     pub imgui: Option<String>,
 }
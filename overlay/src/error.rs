@@ -0,0 +1,23 @@
+use glium::backend::glutin::DisplayCreationError;
+use imgui_glow_renderer::RendererError;
+use thiserror::Error;
+
+pub type Result<T> = std::result::Result<T, OverlayError>;
+
+#[derive(Debug, Error)]
+pub enum OverlayError {
+    #[error("no monitor available to attach the overlay to")]
+    NoMonitorAvailable,
+
+    #[error("failed to create overlay display: {0}")]
+    DisplayError(DisplayCreationError),
+
+    #[error("failed to initialize imgui renderer: {0}")]
+    RenderError(RendererError),
+
+    #[error("failed to allocate headless framebuffer: {0}")]
+    FramebufferAllocation(String),
+
+    #[error(transparent)]
+    WindowsError(#[from] windows::core::Error),
+}
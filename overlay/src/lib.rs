@@ -2,36 +2,48 @@ use clipboard::ClipboardSupport;
 use copypasta::ClipboardContext;
 use error::{Result, OverlayError};
 use glium::glutin;
+use glium::glutin::dpi::{PhysicalPosition, PhysicalSize};
 use glium::glutin::event::{Event, WindowEvent};
-use glium::glutin::event_loop::{ControlFlow, EventLoop};
+use glium::glutin::event_loop::{ControlFlow, EventLoop, EventLoopWindowTarget};
+use glium::glutin::monitor::MonitorHandle;
 use glium::glutin::platform::windows::WindowExtWindows;
 use glium::glutin::window::{WindowBuilder, Window};
-use glium::{Display, Surface};
+use glium::glutin::{ContextError, Robustness};
+use glium::Display;
+use glow::HasContext;
 use imgui::{Context, FontConfig, FontSource, Io};
-use imgui_glium_renderer::Renderer;
+use imgui_glow_renderer::AutoRenderer;
 use imgui_winit_support::{HiDpiMode, WinitPlatform};
 use input::InputSystem;
 use window_tracker::WindowTracker;
 use windows::core::PCSTR;
 use std::ffi::CString;
-use std::time::Instant;
-use windows::Win32::Foundation::{BOOL, HWND};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+use arc_swap::ArcSwap;
+use windows::Win32::Foundation::{BOOL, HWND, POINT, RECT};
 use windows::Win32::Graphics::Dwm::{
     DwmEnableBlurBehindWindow, DWM_BB_BLURREGION, DWM_BB_ENABLE, DWM_BLURBEHIND,
 };
 use windows::Win32::Graphics::Gdi::CreateRectRgn;
+use windows::Win32::UI::HiDpi::GetDpiForWindow;
 use windows::Win32::UI::Input::KeyboardAndMouse::SetActiveWindow;
 use windows::Win32::UI::WindowsAndMessaging::{
-    GetWindowLongPtrA, SetWindowLongA, SetWindowLongPtrA, SetWindowPos,
-    GWL_EXSTYLE, GWL_STYLE, HWND_TOPMOST, SWP_NOMOVE, SWP_NOSIZE, WS_CLIPSIBLINGS,
+    ClientToScreen, FindWindowA, GetClientRect, GetWindowLongPtrA, SetWindowLongA, SetWindowLongPtrA, SetWindowPos,
+    GWL_EXSTYLE, GWL_STYLE, HWND_TOPMOST, SWP_NOACTIVATE, SWP_NOMOVE, SWP_NOSIZE, SWP_NOZORDER, WS_CLIPSIBLINGS,
     WS_EX_LAYERED, WS_EX_NOACTIVATE, WS_EX_TOOLWINDOW, WS_EX_TRANSPARENT, WS_POPUP, WS_VISIBLE, MessageBoxA, MB_ICONERROR, MB_OK, ShowWindow, SW_SHOW,
 };
 
 mod clipboard;
 mod error;
 mod input;
+mod text_shaping;
 mod window_tracker;
 
+pub use text_shaping::{shape_line, ShapedGlyph};
+
 pub fn show_error_message(title: &str, message: &str) {
     let title = CString::new(title).unwrap_or_else(|_| CString::new("[[ NulError ]]").unwrap());
     let message = CString::new(message).unwrap_or_else(|_| CString::new("[[ NulError ]]").unwrap());
@@ -50,33 +62,214 @@ pub struct System {
     pub display: glium::Display,
     pub imgui: Context,
     pub platform: WinitPlatform,
-    pub renderer: Renderer,
+    pub renderer: AutoRenderer,
     pub font_size: f32,
-    pub window_tracker: WindowTracker,
+    /// Only present when the overlay is attached to an on-screen window; a
+    /// headless render target has nothing to keep topmost.
+    pub window_tracker: Option<WindowTracker>,
+    title: String,
+    target: OverlayTarget,
+    target_window: String,
+    render_mode: RenderMode,
 }
 
-pub fn init(title: &str, target_window: &str) -> Result<System> {
-    let window_tracker = WindowTracker::new(target_window)?;
+/// Where finished frames end up.
+enum RenderMode {
+    /// Present frames on the visible, always-on-top overlay window.
+    Window,
+    /// Render into an offscreen framebuffer and hand the resulting RGBA
+    /// pixel buffer to `sink` instead of presenting anything on screen.
+    /// Enables automated visual testing of ESP geometry without a
+    /// GPU-attached desktop and feeding overlay frames into an external
+    /// compositor or capture pipeline.
+    Headless {
+        framebuffer: glow::NativeFramebuffer,
+        size: (u32, u32),
+        sink: Box<dyn FnMut(&[u8], u32, u32)>,
+    },
+}
 
-    let event_loop = EventLoop::new();
-    let context = glutin::ContextBuilder::new().with_vsync(false);
+/// What the overlay window should be sized and positioned to match.
+#[derive(Clone)]
+pub enum OverlayTarget {
+    /// Cover the primary monitor, falling back to the first available one.
+    PrimaryMonitor,
+    /// Cover a specific monitor.
+    Monitor(MonitorHandle),
+    /// Continuously resize/reposition the overlay to match the target
+    /// game window's client rectangle, so ESP stays aligned when the game
+    /// runs windowed, on a secondary monitor, or gets dragged between
+    /// monitors with different DPI scale factors.
+    FollowWindow,
+}
+
+/// Physical position/size the overlay window should currently occupy.
+#[derive(Clone, Copy, PartialEq)]
+struct OverlayRect {
+    position: PhysicalPosition<i32>,
+    size: PhysicalSize<u32>,
+}
+
+/// Looks up `target_window`'s HWND by title. Only needed as a fallback
+/// before `WindowTracker` has resolved and cached the handle itself (e.g.
+/// while building the very first overlay rect in `init`, before the event
+/// loop has run a single `WindowTracker::update`).
+fn find_target_hwnd(target_window: &str) -> Option<HWND> {
+    let title = CString::new(target_window).ok()?;
+    let hwnd = unsafe { FindWindowA(PCSTR(std::ptr::null()), PCSTR::from_raw(title.as_ptr() as *const u8)) };
+    if hwnd.0 == 0 {
+        None
+    } else {
+        Some(hwnd)
+    }
+}
+
+/// Resolves `hwnd`'s client rectangle (i.e. excluding its title bar and
+/// borders, unlike `GetWindowRect`) in screen-space physical pixels, for
+/// `OverlayTarget::FollowWindow`.
+fn resolve_window_client_rect(hwnd: HWND) -> Option<OverlayRect> {
+    let mut rect = RECT::default();
+    if !unsafe { GetClientRect(hwnd, &mut rect) }.as_bool() {
+        return None;
+    }
+
+    let mut origin = POINT { x: rect.left, y: rect.top };
+    if !unsafe { ClientToScreen(hwnd, &mut origin) }.as_bool() {
+        return None;
+    }
+
+    Some(OverlayRect {
+        position: PhysicalPosition::new(origin.x, origin.y),
+        size: PhysicalSize::new(
+            (rect.right - rect.left).max(0) as u32,
+            (rect.bottom - rect.top).max(0) as u32,
+        ),
+    })
+}
+
+/// Physical-to-logical pixel scale factor for `hwnd`'s current monitor, so
+/// `io.display_size` can be kept correct when the overlay is resized by
+/// directly following the target window, bypassing winit's own DPI-aware
+/// resize handling.
+fn window_dpi_scale(hwnd: HWND) -> f32 {
+    const DEFAULT_DPI: u32 = 96;
+    match unsafe { GetDpiForWindow(hwnd) } {
+        0 => 1.0,
+        dpi => dpi as f32 / DEFAULT_DPI as f32,
+    }
+}
 
-    /* TODO: Replace with target which ether is a monitor or a window! */
-    let target_monitor = event_loop
-        .primary_monitor()
-        .or_else(|| event_loop.available_monitors().next())
-        .ok_or(OverlayError::NoMonitorAvailable)?;
+fn resolve_overlay_rect<T>(
+    target: &OverlayTarget,
+    target_window: &str,
+    target_hwnd: Option<HWND>,
+    event_loop: &EventLoopWindowTarget<T>,
+) -> Result<OverlayRect> {
+    match target {
+        OverlayTarget::PrimaryMonitor => {
+            let monitor = event_loop
+                .primary_monitor()
+                .or_else(|| event_loop.available_monitors().next())
+                .ok_or(OverlayError::NoMonitorAvailable)?;
+            Ok(OverlayRect { position: monitor.position(), size: monitor.size() })
+        }
+        OverlayTarget::Monitor(monitor) => {
+            Ok(OverlayRect { position: monitor.position(), size: monitor.size() })
+        }
+        OverlayTarget::FollowWindow => target_hwnd
+            .or_else(|| find_target_hwnd(target_window))
+            .and_then(resolve_window_client_rect)
+            .or_else(|| {
+                event_loop
+                    .primary_monitor()
+                    .or_else(|| event_loop.available_monitors().next())
+                    .map(|monitor| OverlayRect { position: monitor.position(), size: monitor.size() })
+            })
+            .ok_or(OverlayError::NoMonitorAvailable),
+    }
+}
+
+/// Creates the layered, transparent, always-on-top overlay window together
+/// with its `glium::Display`, and applies the window styling from `init`.
+///
+/// Factored out of `init` so the exact same steps can be re-run to rebuild
+/// the `Display` after the GPU context has been lost, without re-creating
+/// the `EventLoop`, `imgui::Context` or `WindowTracker`.
+fn create_display<T>(
+    title: &str,
+    target: &OverlayTarget,
+    target_window: &str,
+    target_hwnd: Option<HWND>,
+    event_loop: &EventLoopWindowTarget<T>,
+) -> Result<Display> {
+    // Without an explicitly robust context, drivers are never obligated to
+    // surface `GL_CONTEXT_LOST_KHR` via `glGetError`, which is what
+    // `is_gl_context_lost` checks for below.
+    let context = glutin::ContextBuilder::new()
+        .with_vsync(false)
+        .with_gl_robustness(Robustness::TryRobustLoseContextOnReset);
+
+    let rect = resolve_overlay_rect(target, target_window, target_hwnd, event_loop)?;
 
     let builder = WindowBuilder::new()
         .with_resizable(false)
         .with_title(title.to_owned())
-        .with_inner_size(target_monitor.size())
-        .with_position(target_monitor.position())
+        .with_inner_size(rect.size)
+        .with_position(rect.position)
         .with_visible(false);
 
-    let display = Display::new(builder, context, &event_loop)
+    let display = Display::new(builder, context, event_loop)
         .map_err(OverlayError::DisplayError)?;
 
+    {
+        let window = display.gl_window();
+        let window = window.window();
+
+        window.set_decorations(false);
+        window.set_undecorated_shadow(false);
+
+        let hwnd = HWND(window.hwnd());
+        unsafe {
+            // Make it transparent
+            SetWindowLongA(
+                hwnd,
+                GWL_STYLE,
+                (WS_POPUP | WS_VISIBLE | WS_CLIPSIBLINGS).0 as i32,
+            );
+            SetWindowLongPtrA(
+                hwnd,
+                GWL_EXSTYLE,
+                (WS_EX_LAYERED | WS_EX_TRANSPARENT | WS_EX_TOOLWINDOW | WS_EX_NOACTIVATE).0
+                    as isize,
+            );
+
+            let mut bb: DWM_BLURBEHIND = Default::default();
+            bb.dwFlags = DWM_BB_ENABLE | DWM_BB_BLURREGION;
+            bb.fEnable = BOOL::from(true);
+            bb.hRgnBlur = CreateRectRgn(0, 0, 1, 1);
+            DwmEnableBlurBehindWindow(hwnd, &bb)?;
+
+            // Move the window to the top
+            SetWindowPos(hwnd, HWND_TOPMOST, 0, 0, 0, 0, SWP_NOMOVE | SWP_NOSIZE);
+        }
+    }
+
+    Ok(display)
+}
+
+/// Creates the `glow::Context` backing the imgui renderer, sharing the GL
+/// context already made current by `display`'s window.
+unsafe fn create_glow_context(display: &Display) -> glow::Context {
+    glow::Context::from_loader_function(|symbol| {
+        display.gl_window().get_proc_address(symbol) as *const _
+    })
+}
+
+/// Builds the imgui context, clipboard, platform glue, font atlas and
+/// `imgui-glow-renderer` shared by both the windowed and headless
+/// construction paths. `text_shaping` mirrors `AppSettings::text_shaping`
+/// (see its doc comment for the rationale).
+fn create_imgui(display: &Display, text_shaping: bool) -> Result<(Context, WinitPlatform, f32, AutoRenderer)> {
     let mut imgui = Context::create();
     imgui.set_ini_filename(None);
 
@@ -99,58 +292,100 @@ pub fn init(title: &str, target_window: &str) -> Result<System> {
     // value (as the scaling is handled by winit)
     let font_size = 18.0;
 
-    imgui.fonts().add_font(&[FontSource::TtfData {
+    let fonts = [FontSource::TtfData {
         data: include_bytes!("../resources/Roboto-Regular.ttf"),
         size_pixels: font_size,
         config: Some(FontConfig {
-            // As imgui-glium-renderer isn't gamma-correct with
-            // it's font rendering, we apply an arbitrary
-            // multiplier to make the font a bit "heavier". With
-            // default imgui-glow-renderer this is unnecessary.
-            rasterizer_multiply: 1.5,
             // Oversampling font helps improve text rendering at
             // expense of larger font atlas texture.
             oversample_h: 4,
             oversample_v: 4,
             ..FontConfig::default()
         }),
-    }]);
+    }];
+
+    // TODO: once a Cyrillic/CJK fallback font asset ships in resources/,
+    // merge it into the atlas here (merge_mode: true, FontGlyphRanges
+    // covering 0x0400-0x052F/0x4E00-0x9FFF/0x3040-0x30FF), and have the
+    // enhancement render paths call `shape_line` for player-name and
+    // bomb-site labels instead of handing imgui raw UTF-8. Neither of those
+    // exists yet, so the setting has no effect today — warn instead of
+    // silently accepting it.
+    if text_shaping {
+        log::warn!(
+            "text_shaping is enabled but not yet wired into any render path; it currently has no effect"
+        );
+    }
 
-    {
-        let window = display.gl_window();
-        let window = window.window();
+    imgui.fonts().add_font(&fonts);
 
-        window.set_decorations(false);
-        window.set_undecorated_shadow(false);
+    // imgui-glow-renderer is gamma-correct, unlike imgui-glium-renderer, so
+    // the previous `rasterizer_multiply` fudge factor is no longer needed.
+    let gl = unsafe { create_glow_context(display) };
+    let renderer = AutoRenderer::initialize(gl, &mut imgui)
+        .map_err(OverlayError::RenderError)?;
 
-        let hwnd = HWND(window.hwnd());
-        unsafe {
-            // Make it transparent
-            SetWindowLongA(
-                hwnd,
-                GWL_STYLE,
-                (WS_POPUP | WS_VISIBLE | WS_CLIPSIBLINGS).0 as i32,
-            );
-            SetWindowLongPtrA(
-                hwnd,
-                GWL_EXSTYLE,
-                (WS_EX_LAYERED | WS_EX_TRANSPARENT | WS_EX_TOOLWINDOW | WS_EX_NOACTIVATE).0
-                    as isize,
-            );
+    Ok((imgui, platform, font_size, renderer))
+}
 
-            let mut bb: DWM_BLURBEHIND = Default::default();
-            bb.dwFlags = DWM_BB_ENABLE | DWM_BB_BLURREGION;
-            bb.fEnable = BOOL::from(true);
-            bb.hRgnBlur = CreateRectRgn(0, 0, 1, 1);
-            DwmEnableBlurBehindWindow(hwnd, &bb)?;
+pub fn init(
+    title: &str,
+    target_window: &str,
+    target: OverlayTarget,
+    text_shaping: bool,
+) -> Result<System> {
+    let window_tracker = WindowTracker::new(target_window)?;
 
-            // Move the window to the top
-            SetWindowPos(hwnd, HWND_TOPMOST, 0, 0, 0, 0, SWP_NOMOVE | SWP_NOSIZE);
-        }
-    }
+    let event_loop = EventLoop::new();
+    let display = create_display(title, &target, target_window, window_tracker.hwnd(), &event_loop)?;
+    let (imgui, platform, font_size, renderer) = create_imgui(&display, text_shaping)?;
 
-    let renderer = Renderer::init(&mut imgui, &display)
-        .map_err(OverlayError::RenderError)?;
+    Ok(System {
+        event_loop,
+        display,
+        imgui,
+        platform,
+        renderer,
+        font_size,
+        window_tracker: Some(window_tracker),
+        title: title.to_owned(),
+        target,
+        target_window: target_window.to_owned(),
+        render_mode: RenderMode::Window,
+    })
+}
+
+/// Builds the overlay's imgui pipeline against an offscreen framebuffer
+/// instead of a visible layered window. `main_loop` will then render each
+/// frame into that framebuffer and hand the resulting RGBA pixel buffer to
+/// `sink` rather than calling `ShowWindow`/`SetWindowPos`. A hidden window
+/// is still created to own the GL context (glutin needs one on Windows),
+/// but it is never shown, styled, or kept topmost.
+pub fn init_headless(
+    title: &str,
+    size: (u32, u32),
+    text_shaping: bool,
+    sink: impl FnMut(&[u8], u32, u32) + 'static,
+) -> Result<System> {
+    let event_loop = EventLoop::new();
+    let context = glutin::ContextBuilder::new()
+        .with_vsync(false)
+        .with_gl_robustness(Robustness::TryRobustLoseContextOnReset);
+    let builder = WindowBuilder::new()
+        .with_resizable(false)
+        .with_title(title.to_owned())
+        .with_inner_size(PhysicalSize::new(size.0, size.1))
+        .with_visible(false);
+    let display = Display::new(builder, context, &event_loop)
+        .map_err(OverlayError::DisplayError)?;
+
+    let (mut imgui, platform, font_size, renderer) = create_imgui(&display, text_shaping)?;
+    imgui.io_mut().display_size = [size.0 as f32, size.1 as f32];
+
+    let (framebuffer, _texture) = unsafe {
+        create_headless_framebuffer(renderer.gl_context(), size)
+            .map_err(OverlayError::FramebufferAllocation)?
+    };
 
     Ok(System {
         event_loop,
@@ -159,10 +394,116 @@ pub fn init(title: &str, target_window: &str) -> Result<System> {
         platform,
         renderer,
         font_size,
-        window_tracker,
+        window_tracker: None,
+        title: title.to_owned(),
+        target: OverlayTarget::PrimaryMonitor,
+        target_window: String::new(),
+        render_mode: RenderMode::Headless { framebuffer, size, sink: Box::new(sink) },
     })
 }
 
+/// `glReadPixels` returns rows bottom-to-top. Flips them in place so the
+/// buffer handed to a `RenderMode::Headless` sink is in the top-down
+/// layout image consumers (and `ESP` geometry comparisons) expect.
+fn flip_rows_vertically(pixels: &mut [u8], width: u32, height: u32) {
+    let stride = width as usize * 4;
+    let mut row = vec![0u8; stride];
+    for top in 0..(height as usize / 2) {
+        let bottom = height as usize - 1 - top;
+        let (top_start, bottom_start) = (top * stride, bottom * stride);
+
+        row.copy_from_slice(&pixels[top_start..top_start + stride]);
+        pixels.copy_within(bottom_start..bottom_start + stride, top_start);
+        pixels[bottom_start..bottom_start + stride].copy_from_slice(&row);
+    }
+}
+
+/// Allocates an RGBA8 texture and wraps it in a framebuffer object so the
+/// renderer can draw into it instead of the default, on-screen framebuffer.
+unsafe fn create_headless_framebuffer(
+    gl: &glow::Context,
+    size: (u32, u32),
+) -> std::result::Result<(glow::NativeFramebuffer, glow::NativeTexture), String> {
+    let texture = gl.create_texture()?;
+    gl.bind_texture(glow::TEXTURE_2D, Some(texture));
+    gl.tex_image_2d(
+        glow::TEXTURE_2D,
+        0,
+        glow::RGBA8 as i32,
+        size.0 as i32,
+        size.1 as i32,
+        0,
+        glow::RGBA,
+        glow::UNSIGNED_BYTE,
+        None,
+    );
+    gl.tex_parameter_i32(glow::TEXTURE_2D, glow::TEXTURE_MIN_FILTER, glow::LINEAR as i32);
+    gl.tex_parameter_i32(glow::TEXTURE_2D, glow::TEXTURE_MAG_FILTER, glow::LINEAR as i32);
+
+    let framebuffer = gl.create_framebuffer()?;
+    gl.bind_framebuffer(glow::FRAMEBUFFER, Some(framebuffer));
+    gl.framebuffer_texture_2d(
+        glow::FRAMEBUFFER,
+        glow::COLOR_ATTACHMENT0,
+        glow::TEXTURE_2D,
+        Some(texture),
+        0,
+    );
+    gl.bind_framebuffer(glow::FRAMEBUFFER, None);
+
+    Ok((framebuffer, texture))
+}
+
+/// Whether `error` indicates the GPU/display context has been lost (e.g.
+/// a fullscreen game mode switch, a display resolution change, a driver
+/// TDR/reset, or a GPU hot-swap) rather than a one-off, unrecoverable bug.
+fn is_context_lost(error: &ContextError) -> bool {
+    matches!(error, ContextError::ContextLost)
+}
+
+/// `GL_CONTEXT_LOST_KHR` from `GL_KHR_robustness`. `glow` doesn't expose
+/// this as a named constant, so the raw error code is checked directly.
+const GL_CONTEXT_LOST_KHR: u32 = 0x0507;
+
+/// Whether the last GL error on `gl` indicates the context has been lost.
+/// `imgui_glow_renderer::Renderer::render` surfaces GPU context loss this
+/// way rather than through a typed error, unlike `Display::swap_buffers`.
+fn is_gl_context_lost(gl: &glow::Context) -> bool {
+    unsafe { gl.get_error() == GL_CONTEXT_LOST_KHR }
+}
+
+/// Small retry/backoff state machine driving display rebuilds after a lost
+/// GPU context, so transient losses recover within a frame or two instead
+/// of tearing down the whole overlay.
+struct ContextRecovery {
+    consecutive_failures: u32,
+    max_attempts: u32,
+}
+
+impl ContextRecovery {
+    fn new() -> Self {
+        Self { consecutive_failures: 0, max_attempts: 10 }
+    }
+
+    /// Records a context loss and reports whether another rebuild attempt
+    /// should be made, sleeping for an increasing backoff in between.
+    fn note_failure(&mut self) -> bool {
+        self.consecutive_failures += 1;
+        if self.consecutive_failures > self.max_attempts {
+            return false;
+        }
+
+        let backoff = Duration::from_millis(50 * self.consecutive_failures as u64)
+            .min(Duration::from_secs(2));
+        std::thread::sleep(backoff);
+        true
+    }
+
+    fn note_recovered(&mut self) {
+        self.consecutive_failures = 0;
+    }
+}
+
 /// Toggles the overlay noactive and transparent state
 /// according to whenever ImGui wants mouse/cursor grab.
 struct OverlayActiveTracker {
@@ -199,28 +540,106 @@ impl OverlayActiveTracker {
     }
 }
 
+/// Handle to a dedicated thread which repeatedly runs a caller-supplied
+/// `update` closure and publishes each result by atomically swapping it into
+/// an `ArcSwap` slot, so the render thread never blocks on whatever `update`
+/// does (e.g. a possibly slow memory read against the game).
+struct UpdateThread<S> {
+    snapshot: Arc<ArcSwap<S>>,
+    shutdown: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl<S: Send + Sync + 'static> UpdateThread<S> {
+    /// Spawns the update thread. `update` is expected to build a fresh,
+    /// immutable snapshot of everything the renderers need (player list,
+    /// bomb state, trigger/aim decisions, ...) on every call.
+    fn spawn<U>(initial: S, mut update: U) -> Self
+    where
+        U: FnMut() -> S + Send + 'static,
+    {
+        let snapshot = Arc::new(ArcSwap::from_pointee(initial));
+        let shutdown = Arc::new(AtomicBool::new(false));
+
+        let handle = {
+            let snapshot = snapshot.clone();
+            let shutdown = shutdown.clone();
+            std::thread::Builder::new()
+                .name("enhancement-update".to_owned())
+                .spawn(move || {
+                    while !shutdown.load(Ordering::Acquire) {
+                        let next = update();
+                        snapshot.store(Arc::new(next));
+                    }
+                })
+                .expect("failed to spawn enhancement update thread")
+        };
+
+        Self { snapshot, shutdown, handle: Some(handle) }
+    }
+
+    /// Returns the most recently published snapshot without ever blocking
+    /// on the update thread.
+    fn load(&self) -> arc_swap::Guard<Arc<S>> {
+        self.snapshot.load()
+    }
+
+    /// Signals the update thread to stop and waits for it to exit.
+    fn shutdown(&mut self) {
+        self.shutdown.store(true, Ordering::Release);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
 impl System {
-    pub fn main_loop<U, R>(self, mut update: U, mut render: R) -> !
+    /// Runs the overlay's event loop.
+    ///
+    /// `update` is executed on a dedicated thread as fast as it can run and
+    /// must produce a fresh, self contained snapshot of type `S` on every
+    /// call. `render` is executed on the winit/GL thread for every
+    /// `RedrawRequested` and only ever reads the most recently published
+    /// snapshot, so a slow game memory read can no longer stall frame
+    /// delivery.
+    pub fn main_loop<S, U, R>(self, initial_snapshot: S, update: U, mut render: R) -> !
     where
-        U: FnMut(&mut imgui::Context) -> bool + 'static,
-        R: FnMut(&mut imgui::Ui) -> bool + 'static,
+        S: Send + Sync + 'static,
+        U: FnMut() -> S + Send + 'static,
+        R: FnMut(&mut imgui::Ui, &S) -> bool + 'static,
     {
         let System {
             event_loop,
-            display,
+            mut display,
             mut imgui,
             mut platform,
             mut renderer,
             mut window_tracker,
+            title,
+            target,
+            target_window,
+            mut render_mode,
             ..
         } = self;
         let mut last_frame = Instant::now();
+        let mut overlay_rect = OverlayRect {
+            position: PhysicalPosition::new(0, 0),
+            size: PhysicalSize::new(0, 0),
+        };
 
         let mut active_tracker = OverlayActiveTracker::new();
         let mut input_system = InputSystem::new();
         let mut initial_render = true;
+        let mut context_recovery = ContextRecovery::new();
+
+        let mut update_thread = UpdateThread::spawn(initial_snapshot, update);
 
-        event_loop.run(move |event, _, control_flow| match event {
+        let mut shutdown = move |control_flow: &mut ControlFlow, update_thread: &mut UpdateThread<S>| {
+            update_thread.shutdown();
+            *control_flow = ControlFlow::Exit;
+        };
+
+        event_loop.run(move |event, window_target, control_flow| match event {
             Event::NewEvents(_) => {
                 let now = Instant::now();
                 imgui.io_mut().update_delta_time(now - last_frame);
@@ -229,6 +648,7 @@ impl System {
             Event::MainEventsCleared => {
                 let gl_window = display.gl_window();
                 if let Err(error) = platform.prepare_frame(imgui.io_mut(), gl_window.window()) {
+                    update_thread.shutdown();
                     *control_flow = ControlFlow::ExitWithCode(1);
                     log::error!("Platform implementation prepare_frame failed: {}", error);
                     return;
@@ -237,11 +657,43 @@ impl System {
                 let window = gl_window.window();
                 input_system.update(window, imgui.io_mut());
                 active_tracker.update(window, imgui.io());
-                window_tracker.update(window);
+                if let Some(window_tracker) = window_tracker.as_mut() {
+                    window_tracker.update(window);
+                }
 
-                if !update(&mut imgui) {
-                    *control_flow = ControlFlow::Exit;
-                    return;
+                if matches!(target, OverlayTarget::FollowWindow) {
+                    let target_hwnd = window_tracker
+                        .as_ref()
+                        .and_then(|t| t.hwnd())
+                        .or_else(|| find_target_hwnd(&target_window));
+                    if let Some(target_hwnd) = target_hwnd {
+                        if let Some(rect) = resolve_window_client_rect(target_hwnd) {
+                            if rect.position != overlay_rect.position || rect.size != overlay_rect.size {
+                                overlay_rect = rect;
+                                let hwnd = HWND(window.hwnd());
+                                unsafe {
+                                    SetWindowPos(
+                                        hwnd,
+                                        HWND::default(),
+                                        overlay_rect.position.x,
+                                        overlay_rect.position.y,
+                                        overlay_rect.size.width as i32,
+                                        overlay_rect.size.height as i32,
+                                        SWP_NOACTIVATE | SWP_NOZORDER,
+                                    );
+                                }
+
+                                // winit won't see this resize (we bypassed its
+                                // window-resize path via SetWindowPos), so keep
+                                // imgui's logical display size in sync ourselves.
+                                let scale = window_dpi_scale(target_hwnd);
+                                imgui.io_mut().display_size = [
+                                    overlay_rect.size.width as f32 / scale,
+                                    overlay_rect.size.height as f32 / scale,
+                                ];
+                            }
+                        }
+                    }
                 }
 
                 window.request_redraw();
@@ -250,38 +702,124 @@ impl System {
                 let gl_window = display.gl_window();
                 let ui = imgui.frame();
 
-                let mut run = render(ui);
+                let snapshot = update_thread.load();
+                let mut run = render(ui, &snapshot);
 
-                let mut target = display.draw();
-                target.clear_all((0.0, 0.0, 0.0, 0.0), 0.0, 0);
                 platform.prepare_render(ui, gl_window.window());
-
                 let draw_data = imgui.render();
 
-                if let Err(error) = renderer.render(&mut target, draw_data) {
-                    log::error!("Failed to render ImGui draw data: {}", error);
-                    run = false;
-                } else if let Err(error) = target.finish() {
-                    log::error!("Failed to swap render buffers: {}", error);
-                    run = false;
-                }
-                
-                if !run {
-                    *control_flow = ControlFlow::Exit;
+                match &mut render_mode {
+                    RenderMode::Window => {
+                        unsafe {
+                            let gl = renderer.gl_context();
+                            gl.bind_framebuffer(glow::FRAMEBUFFER, None);
+                            gl.clear_color(0.0, 0.0, 0.0, 0.0);
+                            gl.clear(glow::COLOR_BUFFER_BIT);
+                        }
+
+                        let mut context_lost = false;
+                        if let Err(error) = renderer.render(draw_data) {
+                            // `gl` is re-fetched rather than reused from above: that
+                            // borrow had to end before `renderer.render` could take
+                            // `renderer` mutably.
+                            context_lost = is_gl_context_lost(renderer.gl_context());
+                            log::error!("Failed to render ImGui draw data: {}", error);
+                            run = false;
+                        } else if let Err(error) = display.gl_window().swap_buffers() {
+                            context_lost = is_context_lost(&error);
+                            log::error!("Failed to swap render buffers: {}", error);
+                            run = false;
+                        }
+
+                        if context_lost {
+                            run = true;
+                            if context_recovery.note_failure() {
+                                log::warn!(
+                                    "GPU context lost, rebuilding display (attempt {}/{})",
+                                    context_recovery.consecutive_failures,
+                                    context_recovery.max_attempts
+                                );
+                                let target_hwnd = window_tracker.as_ref().and_then(|t| t.hwnd());
+                                match create_display(&title, &target, &target_window, target_hwnd, window_target) {
+                                    Ok(new_display) => {
+                                        let gl = unsafe { create_glow_context(&new_display) };
+                                        match AutoRenderer::initialize(gl, &mut imgui) {
+                                            Ok(new_renderer) => {
+                                                display = new_display;
+                                                renderer = new_renderer;
+                                                initial_render = true;
+                                            }
+                                            Err(error) => {
+                                                log::error!("Failed to recreate ImGui renderer after context loss: {}", error);
+                                                run = false;
+                                            }
+                                        }
+                                    }
+                                    Err(error) => {
+                                        log::error!("Failed to recreate display after context loss: {}", error);
+                                        run = false;
+                                    }
+                                }
+                            } else {
+                                log::error!(
+                                    "GPU context repeatedly lost; giving up after {} attempts",
+                                    context_recovery.max_attempts
+                                );
+                                run = false;
+                            }
+                        } else if run {
+                            context_recovery.note_recovered();
+                        }
+
+                        if initial_render {
+                            let gl_window = display.gl_window();
+                            // Note:
+                            // We can not use `gl_window.window().set_visible(true)` as this will prevent the overlay
+                            // to be click trough...
+                            unsafe { ShowWindow(HWND(gl_window.window().hwnd() as isize), SW_SHOW); }
+                        }
+                    }
+                    RenderMode::Headless { framebuffer, size, sink } => {
+                        unsafe {
+                            let gl = renderer.gl_context();
+                            gl.bind_framebuffer(glow::FRAMEBUFFER, Some(*framebuffer));
+                            gl.clear_color(0.0, 0.0, 0.0, 0.0);
+                            gl.clear(glow::COLOR_BUFFER_BIT);
+                        }
+
+                        if let Err(error) = renderer.render(draw_data) {
+                            log::error!("Failed to render ImGui draw data: {}", error);
+                            run = false;
+                        } else {
+                            let mut pixels = vec![0u8; size.0 as usize * size.1 as usize * 4];
+                            unsafe {
+                                let gl = renderer.gl_context();
+                                gl.read_pixels(
+                                    0,
+                                    0,
+                                    size.0 as i32,
+                                    size.1 as i32,
+                                    glow::RGBA,
+                                    glow::UNSIGNED_BYTE,
+                                    glow::PixelPackData::Slice(&mut pixels),
+                                );
+                                gl.bind_framebuffer(glow::FRAMEBUFFER, None);
+                            }
+                            flip_rows_vertically(&mut pixels, size.0, size.1);
+                            sink(&pixels, size.0, size.1);
+                        }
+                    }
                 }
 
-                if initial_render {
-                    initial_render = false;
-                    // Note:
-                    // We can not use `gl_window.window().set_visible(true)` as this will prevent the overlay
-                    // to be click trough...
-                    unsafe { ShowWindow(HWND(gl_window.window().hwnd() as isize), SW_SHOW); }
+                initial_render = false;
+                if !run {
+                    shutdown(control_flow, &mut update_thread);
                 }
             }
             Event::WindowEvent {
                 event: WindowEvent::CloseRequested,
                 ..
-            } => *control_flow = ControlFlow::Exit,
+            } => shutdown(control_flow, &mut update_thread),
             event => {
                 let gl_window = display.gl_window();
                 platform.handle_event(imgui.io_mut(), gl_window.window(), &event);
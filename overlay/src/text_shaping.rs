@@ -0,0 +1,74 @@
+use rustybuzz::{Face, UnicodeBuffer};
+
+/// A single shaped glyph, in pixels at the requested font size. Offsets are
+/// relative to the pen position, which moves by each preceding glyph's advance.
+pub struct ShapedGlyph {
+    pub glyph_id: u32,
+    pub x_advance: f32,
+    pub y_advance: f32,
+    pub x_offset: f32,
+    pub y_offset: f32,
+}
+
+/// Shapes `text` with `face`, producing correctly positioned glyphs for
+/// complex scripts (e.g. Cyrillic, CJK) instead of imgui's naive per-codepoint
+/// layout. See `AppSettings::text_shaping` for when this is meant to be used.
+pub fn shape_line(face: &Face, text: &str, font_size_px: f32) -> Vec<ShapedGlyph> {
+    let mut buffer = UnicodeBuffer::new();
+    buffer.push_str(text);
+    let buffer = buffer.guess_segment_properties();
+
+    let glyph_buffer = rustybuzz::shape(face, &[], buffer);
+    let scale = font_size_px / face.units_per_em() as f32;
+
+    glyph_buffer
+        .glyph_infos()
+        .iter()
+        .zip(glyph_buffer.glyph_positions())
+        .map(|(info, pos)| ShapedGlyph {
+            glyph_id: info.glyph_id,
+            x_advance: pos.x_advance as f32 * scale,
+            y_advance: pos.y_advance as f32 * scale,
+            x_offset: pos.x_offset as f32 * scale,
+            y_offset: pos.y_offset as f32 * scale,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn test_face(bytes: &[u8]) -> Face {
+        Face::from_slice(bytes, 0).expect("bundled test font should parse")
+    }
+
+    #[test]
+    fn empty_string_shapes_to_no_glyphs() {
+        let data = include_bytes!("../resources/Roboto-Regular.ttf");
+        let face = test_face(data);
+
+        let glyphs = shape_line(&face, "", 18.0);
+        assert!(glyphs.is_empty());
+    }
+
+    #[test]
+    fn single_glyph_has_a_nonzero_advance() {
+        let data = include_bytes!("../resources/Roboto-Regular.ttf");
+        let face = test_face(data);
+
+        let glyphs = shape_line(&face, "A", 18.0);
+        assert_eq!(glyphs.len(), 1);
+        assert_ne!(glyphs[0].glyph_id, 0);
+        assert!(glyphs[0].x_advance > 0.0);
+    }
+
+    #[test]
+    fn glyph_count_matches_codepoint_count_for_simple_latin_text() {
+        let data = include_bytes!("../resources/Roboto-Regular.ttf");
+        let face = test_face(data);
+
+        let glyphs = shape_line(&face, "Valthrun", 18.0);
+        assert_eq!(glyphs.len(), "Valthrun".chars().count());
+    }
+}